@@ -0,0 +1,59 @@
+use crate::primitives::{Address, Bytes, U256};
+
+/// Everything a `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL` needs in order for the host to
+/// execute it; built by `instructions::control::call` and carried on
+/// [`InterpreterAction::Call`](crate::InterpreterAction::Call).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallInputs {
+    /// Address of the contract being called.
+    pub contract: Address,
+    /// Value the callee sees as `msg.value`. For `DELEGATECALL` this is inherited unchanged from
+    /// the current frame, *not* a fresh value to move - see [`Self::transfers_value`], which is
+    /// `false` in that case precisely so a host can't mistake this for an amount to transfer.
+    pub transfer_value: U256,
+    /// Whether [`Self::transfer_value`] should actually be moved from the caller's balance to the
+    /// callee's. `true` for `CALL`/`CALLCODE`; `false` for `STATICCALL` (value is always zero
+    /// anyway) and `DELEGATECALL` (the current frame's own `msg.value` just carries through
+    /// unchanged for the callee to read - moving it again here would double-transfer funds that
+    /// already moved into this frame).
+    pub transfers_value: bool,
+    /// Calldata sliced out of the caller's memory.
+    pub input: Bytes,
+    /// Gas requested for the callee. This is only the operand off the stack - the interpreter
+    /// does not deduct it from the caller's own [`ComputeMeter`](crate::ComputeMeter); the
+    /// executor owns deciding how much of it to actually forward and accounting for what the
+    /// callee spends.
+    pub gas_limit: u64,
+    /// `context_address` the callee should execute as (differs from `contract` for
+    /// `DELEGATECALL`/`CALLCODE`).
+    pub context_address: Address,
+    /// Caller as seen by the callee - the current frame's own address, except for `DELEGATECALL`
+    /// which forwards the current frame's own caller so the callee sees the same `msg.sender`.
+    pub caller: Address,
+    /// Whether the callee must run in static (no state-change) mode.
+    pub is_static: bool,
+    /// Offset in the caller's memory the callee's output should be copied to.
+    pub ret_offset: usize,
+    /// Max number of bytes of the callee's output to copy to `ret_offset`; the callee's output
+    /// is truncated (not padded) to this length.
+    pub ret_len: usize,
+}
+
+/// Everything a `CREATE`/`CREATE2` needs in order for the host to execute it; built by
+/// `instructions::control::create` and carried on
+/// [`InterpreterAction::Create`](crate::InterpreterAction::Create).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateInputs {
+    /// Caller/deployer address.
+    pub caller: Address,
+    /// Value transferred to the newly created contract.
+    pub value: U256,
+    /// Initcode sliced out of the caller's memory.
+    pub init_code: Bytes,
+    /// Gas requested for the init code. As with [`CallInputs::gas_limit`], this is only the
+    /// caller's remaining balance at the point of `CREATE`/`CREATE2` - the interpreter does not
+    /// deduct it from its own meter; the executor owns accounting for what deployment spends.
+    pub gas_limit: u64,
+    /// `Some(salt)` for `CREATE2`, `None` for `CREATE`.
+    pub salt: Option<U256>,
+}
@@ -0,0 +1,423 @@
+//! `JUMP`/`JUMPI`/`STOP`/`RETURN`/`REVERT` and the CALL/CREATE family.
+//!
+//! The CALL/CREATE opcodes build their [`CallInputs`]/[`CreateInputs`] and record them on
+//! `interp.next_action` instead of invoking the `Host` directly, so `Interpreter::run` can
+//! suspend the frame and let the outer executor own the call stack (see
+//! [`InterpreterAction`](crate::InterpreterAction)).
+
+use super::InstructionResult;
+use crate::opcode;
+use crate::primitives::{Address, U256};
+use crate::{CallInputs, ComputeMeter, CreateInputs, Interpreter, InterpreterAction, MAX_INITCODE_SIZE};
+use alloc::boxed::Box;
+
+pub fn stop(interp: &mut Interpreter) {
+    interp.instruction_result = InstructionResult::Stop;
+}
+
+pub fn jump(interp: &mut Interpreter) {
+    let Ok(target) = interp.stack.pop() else {
+        interp.instruction_result = InstructionResult::StackUnderflow;
+        return;
+    };
+    jump_to(interp, target);
+}
+
+pub fn jumpi(interp: &mut Interpreter) {
+    let Ok([target, cond]) = interp.stack.pop_n::<2>() else {
+        interp.instruction_result = InstructionResult::StackUnderflow;
+        return;
+    };
+    if !cond.is_zero() {
+        jump_to(interp, target);
+    }
+}
+
+/// Validate `target` as a jump destination, recording `(pc, target)` on
+/// `interp.last_invalid_jump` before failing.
+///
+/// Both halves of that pair have to be captured here: the destination is already off the stack
+/// by the time the frame stops, and `instruction_pointer` has by then moved one byte past this
+/// `JUMP`/`JUMPI` (`step` advances it before dispatching), so `program_counter() - 1` is this
+/// instruction's own pc.
+pub(crate) fn jump_to(interp: &mut Interpreter, target: U256) {
+    let target_pc = usize::try_from(target).unwrap_or(usize::MAX);
+    if !interp.contract.bytecode.is_valid_jump(target_pc) {
+        let pc = interp.program_counter().saturating_sub(1);
+        interp.last_invalid_jump = Some((pc, target_pc));
+        interp.instruction_result = InstructionResult::InvalidJump;
+        return;
+    }
+    // Safety: `is_valid_jump` only returns `true` for offsets within the bytecode.
+    interp.instruction_pointer =
+        unsafe { interp.contract.bytecode.bytecode().as_ptr().add(target_pc) };
+}
+
+/// Shared by `RETURN` and `REVERT`: both just slice `[offset, offset + len)` out of memory as
+/// the output and stop the frame.
+pub fn return_(interp: &mut Interpreter, result: InstructionResult) {
+    let Ok([offset, len]) = interp.stack.pop_n::<2>() else {
+        interp.instruction_result = InstructionResult::StackUnderflow;
+        return;
+    };
+    let (Ok(offset), Ok(len)) = (usize::try_from(offset), usize::try_from(len)) else {
+        interp.instruction_result = InstructionResult::OutOfGas;
+        return;
+    };
+    // An empty return never touches memory, so use the sentinel `return_value` already checks for
+    // instead of computing `offset + len` - for a huge `offset` (valid, since nothing is ever read
+    // at it) that addition would overflow despite there being no actual out-of-bounds access.
+    interp.return_range = if len == 0 {
+        usize::MAX..usize::MAX
+    } else {
+        match offset.checked_add(len) {
+            Some(end) => offset..end,
+            None => {
+                interp.instruction_result = InstructionResult::OutOfGas;
+                return;
+            }
+        }
+    };
+    interp.instruction_result = result;
+}
+
+/// `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL`.
+///
+/// Builds the `CallInputs` and suspends the frame via `next_action` instead of calling into the
+/// host; pushes a new `shared_memory` context for the callee, which the callee frees itself via
+/// `return_value` on its way out (`Interpreter::resume_with` does not pop it again).
+pub fn call(opcode: u8, interp: &mut Interpreter) {
+    let has_value = opcode == opcode::CALL || opcode == opcode::CALLCODE;
+    if !interp.stack.has(if has_value { 7 } else { 6 }) {
+        interp.instruction_result = InstructionResult::StackUnderflow;
+        return;
+    }
+
+    // `has` above already guarantees every pop below succeeds.
+    let gas_limit = interp.stack.pop().expect("stack depth already checked");
+    let to = interp.stack.pop().expect("stack depth already checked");
+    let value = if has_value {
+        interp.stack.pop().expect("stack depth already checked")
+    } else {
+        U256::ZERO
+    };
+    let [args_offset, args_len, ret_offset, ret_len] = interp
+        .stack
+        .pop_n::<4>()
+        .expect("stack depth already checked");
+
+    // EIP-214: only a plain `CALL` is barred from transferring value inside a static frame -
+    // `CALLCODE` never touches another account's balance (it runs the target's code against the
+    // caller's own storage/balance), so it's exempt even though it also carries a value operand.
+    if interp.is_static && !value.is_zero() && opcode == opcode::CALL {
+        interp.last_static_violation_pc = Some(interp.program_counter().saturating_sub(1));
+        interp.instruction_result = InstructionResult::CallNotAllowedInsideStatic;
+        return;
+    }
+
+    let (Ok(args_offset), Ok(args_len), Ok(ret_offset), Ok(ret_len)) = (
+        usize::try_from(args_offset),
+        usize::try_from(args_len),
+        usize::try_from(ret_offset),
+        usize::try_from(ret_len),
+    ) else {
+        interp.instruction_result = InstructionResult::OutOfGas;
+        return;
+    };
+
+    let contract = address_from_word(to);
+    let is_static = interp.is_static || opcode == opcode::STATICCALL;
+    let context_address = if opcode == opcode::DELEGATECALL || opcode == opcode::CALLCODE {
+        interp.contract.address
+    } else {
+        contract
+    };
+    // DELEGATECALL runs the callee's code in the caller's own context, so the callee must see the
+    // same `msg.sender`/`msg.value` this frame was itself invoked with, instead of this frame's
+    // own address and a fresh (zero) value - and since that value already moved into this frame,
+    // it must not be transferred again.
+    let (caller, transfer_value, transfers_value) = if opcode == opcode::DELEGATECALL {
+        (interp.contract.caller, interp.contract.value, false)
+    } else {
+        (interp.contract.address, value, has_value)
+    };
+
+    let Some(input) = interp
+        .shared_memory
+        .borrow_mut()
+        .get_slice(args_offset, args_len)
+        .map(|slice| slice.to_vec())
+    else {
+        // `args_offset + args_len` overflowed `usize` - a valid (if absurd) pair of stack
+        // operands, but not an address range memory could ever actually hold.
+        interp.instruction_result = InstructionResult::OutOfGas;
+        return;
+    };
+
+    let inputs = CallInputs {
+        contract,
+        transfer_value,
+        transfers_value,
+        input: input.into(),
+        gas_limit: gas_limit.try_into().unwrap_or(u64::MAX),
+        context_address,
+        caller,
+        is_static,
+        ret_offset,
+        ret_len,
+    };
+
+    interp.shared_memory.borrow_mut().new_context_memory();
+    interp.pending_call_return = Some((ret_offset, ret_len));
+    interp.next_action = InterpreterAction::Call {
+        inputs: Box::new(inputs),
+    };
+}
+
+/// `CREATE`/`CREATE2`.
+///
+/// The EIP-3860 initcode-size check happens here, in the caller's own frame, before a child
+/// interpreter would even be spawned for it - so unlike the deployed-code-size check (which only
+/// the host can evaluate, once the child has returned), this one can be recorded and classified
+/// accurately from within `Interpreter` alone.
+pub fn create(opcode: u8, interp: &mut Interpreter) {
+    if interp.is_static {
+        interp.last_static_violation_pc = Some(interp.program_counter().saturating_sub(1));
+        interp.instruction_result = InstructionResult::StateChangeDuringStaticCall;
+        return;
+    }
+
+    if !interp.stack.has(if opcode == opcode::CREATE2 { 4 } else { 3 }) {
+        interp.instruction_result = InstructionResult::StackUnderflow;
+        return;
+    }
+    // `has` above already guarantees every pop below succeeds.
+    let [value, offset, len] = interp
+        .stack
+        .pop_n::<3>()
+        .expect("stack depth already checked");
+    let salt = (opcode == opcode::CREATE2)
+        .then(|| interp.stack.pop().expect("stack depth already checked"));
+
+    let (Ok(offset), Ok(len)) = (usize::try_from(offset), usize::try_from(len)) else {
+        interp.instruction_result = InstructionResult::OutOfGas;
+        return;
+    };
+
+    if len > MAX_INITCODE_SIZE {
+        interp.last_initcode_len = Some(len);
+        interp.instruction_result = InstructionResult::CreateInitcodeSizeLimit;
+        return;
+    }
+
+    let Some(init_code) = interp
+        .shared_memory
+        .borrow_mut()
+        .get_slice(offset, len)
+        .map(|slice| slice.to_vec())
+    else {
+        // `offset + len` overflowed `usize` - same class of bug as the one guarded against in
+        // `call` above, just for the initcode range instead of the calldata range.
+        interp.instruction_result = InstructionResult::OutOfGas;
+        return;
+    };
+    let init_code = init_code.into();
+
+    let inputs = CreateInputs {
+        caller: interp.contract.address,
+        value,
+        init_code,
+        gas_limit: interp.gas.borrow().remaining(),
+        salt,
+    };
+
+    interp.shared_memory.borrow_mut().new_context_memory();
+    interp.next_action = InterpreterAction::Create {
+        inputs: Box::new(inputs),
+    };
+}
+
+fn address_from_word(word: U256) -> Address {
+    Address::from_slice(&word.to_be_bytes::<32>()[12..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::interp_with_code;
+
+    #[test]
+    fn call_suspends_via_next_action_instead_of_recursing() {
+        let mut interp = interp_with_code(&[opcode::CALL]);
+        // CALL stack order is gas, to, value, argsOffset, argsLength, retOffset, retLength, with
+        // `gas` as the topmost (first popped); push bottom-to-top so the last value pushed ends
+        // up on top.
+        for v in [0u64, 0, 0, 0, 0, 0, 1000] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        call(opcode::CALL, &mut interp);
+
+        assert!(
+            matches!(interp.next_action, InterpreterAction::Call { .. }),
+            "CALL must suspend via next_action, not execute inline"
+        );
+        assert_eq!(interp.shared_memory.borrow().len(), 0, "new context starts empty");
+    }
+
+    #[test]
+    fn call_does_not_reject_callcode_transferring_value_inside_a_static_frame() {
+        // EIP-214 only restricts plain CALL; CALLCODE never touches another account's balance.
+        let mut interp = interp_with_code(&[opcode::CALLCODE]);
+        interp.is_static = true;
+        // Push bottom-to-top: retLength, retOffset, argsLength, argsOffset, value=1, to, gas.
+        for v in [0u64, 0, 0, 0, 1, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        call(opcode::CALLCODE, &mut interp);
+
+        assert!(
+            matches!(interp.next_action, InterpreterAction::Call { .. }),
+            "CALLCODE must not be rejected for carrying value in a static frame"
+        );
+    }
+
+    #[test]
+    fn call_rejects_plain_call_transferring_value_inside_a_static_frame() {
+        let mut interp = interp_with_code(&[opcode::CALL]);
+        interp.is_static = true;
+        // Push bottom-to-top: retLength, retOffset, argsLength, argsOffset, value=1, to, gas.
+        for v in [0u64, 0, 0, 0, 1, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        call(opcode::CALL, &mut interp);
+
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::CallNotAllowedInsideStatic
+        );
+    }
+
+    #[test]
+    fn call_delegatecall_inherits_the_current_frames_caller_and_value() {
+        let mut interp = interp_with_code(&[opcode::DELEGATECALL]);
+        interp.contract.caller = Address::with_last_byte(0xaa);
+        interp.contract.value = U256::from(42);
+        // DELEGATECALL has no value operand: gas, to, argsOffset, argsLength, retOffset,
+        // retLength, with `gas` topmost; push bottom-to-top.
+        for v in [0u64, 0, 0, 0, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        call(opcode::DELEGATECALL, &mut interp);
+
+        let InterpreterAction::Call { inputs } = &interp.next_action else {
+            panic!("DELEGATECALL must suspend via next_action");
+        };
+        assert_eq!(
+            inputs.caller,
+            Address::with_last_byte(0xaa),
+            "callee must see the current frame's own caller as msg.sender, not its address"
+        );
+        assert_eq!(
+            inputs.transfer_value,
+            U256::from(42),
+            "callee must see the current frame's own value as msg.value"
+        );
+        assert!(
+            !inputs.transfers_value,
+            "DELEGATECALL must never cause transfer_value to actually be moved"
+        );
+    }
+
+    #[test]
+    fn call_with_huge_args_offset_does_not_overflow_or_panic() {
+        let mut interp = interp_with_code(&[opcode::CALL]);
+        // Push bottom-to-top: retLength=0, retOffset=0, argsLength=1, argsOffset=huge, value=0,
+        // to=0, gas=0 - `argsOffset + argsLength` overflows `usize` despite a tiny `argsLength`.
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::from(1u64)).unwrap();
+        interp.stack.push(U256::from(usize::MAX)).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        call(opcode::CALL, &mut interp);
+
+        assert_eq!(interp.instruction_result, InstructionResult::OutOfGas);
+        assert!(matches!(interp.next_action, InterpreterAction::None));
+    }
+
+    #[test]
+    fn create_suspends_via_next_action() {
+        let mut interp = interp_with_code(&[opcode::CREATE]);
+        for v in [0u64, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        create(opcode::CREATE, &mut interp);
+        assert!(matches!(interp.next_action, InterpreterAction::Create { .. }));
+    }
+
+    #[test]
+    fn create_rejects_oversized_initcode_in_its_own_frame() {
+        let mut interp = interp_with_code(&[opcode::CREATE]);
+        let len = crate::MAX_INITCODE_SIZE + 1;
+        // Pop order is value, offset, length, with `value` on top; push bottom-to-top.
+        for v in [len as u64, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        create(opcode::CREATE, &mut interp);
+        assert_eq!(interp.instruction_result, InstructionResult::CreateInitcodeSizeLimit);
+        assert_eq!(interp.last_initcode_len, Some(len));
+        assert!(
+            matches!(interp.next_action, InterpreterAction::None),
+            "oversized initcode must never reach the host"
+        );
+    }
+
+    #[test]
+    fn create_with_huge_offset_does_not_overflow_or_panic() {
+        let mut interp = interp_with_code(&[opcode::CREATE]);
+        // Pop order is value, offset, length, with `value` on top; push bottom-to-top so length
+        // goes first and value ends up on top. `length = 1` (not 0) so `offset + length` actually
+        // overflows instead of landing back on `offset` unchanged.
+        interp.stack.push(U256::from(1u64)).unwrap(); // length = 1
+        interp.stack.push(U256::from(usize::MAX)).unwrap(); // offset = huge
+        interp.stack.push(U256::from(1u64)).unwrap(); // value = 1
+        create(opcode::CREATE, &mut interp);
+
+        assert_eq!(interp.instruction_result, InstructionResult::OutOfGas);
+        assert!(matches!(interp.next_action, InterpreterAction::None));
+    }
+
+    #[test]
+    fn return_with_zero_length_and_a_huge_offset_does_not_overflow_or_panic() {
+        let mut interp = interp_with_code(&[opcode::RETURN]);
+        // Stack order is offset, length, with `offset` on top; push bottom-to-top.
+        for v in [0u64, usize::MAX as u64] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        return_(&mut interp, InstructionResult::Return);
+
+        assert_eq!(interp.instruction_result, InstructionResult::Return);
+        assert_eq!(interp.return_range, usize::MAX..usize::MAX);
+        assert_eq!(interp.return_value(), crate::primitives::Bytes::new());
+    }
+
+    #[test]
+    fn jump_to_valid_jumpdest_moves_the_instruction_pointer() {
+        let mut interp = interp_with_code(&[opcode::JUMPDEST, opcode::STOP]);
+        jump_to(&mut interp, U256::ZERO);
+        assert_eq!(interp.program_counter(), 0);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+    }
+
+    #[test]
+    fn jump_to_invalid_target_records_the_jumps_own_pc_not_the_instruction_after_it() {
+        // JUMP at pc 0; simulate `step` having already advanced the pointer to pc 1 before
+        // dispatching here, exactly like the real interpreter loop does.
+        let mut interp = interp_with_code(&[opcode::JUMP, opcode::STOP]);
+        interp.instruction_pointer = unsafe { interp.instruction_pointer.add(1) };
+        jump_to(&mut interp, U256::from(7));
+        assert_eq!(interp.instruction_result, InstructionResult::InvalidJump);
+        assert_eq!(interp.last_invalid_jump, Some((0, 7)));
+    }
+}
@@ -0,0 +1,77 @@
+pub mod control;
+
+use crate::opcode;
+use crate::primitives::Spec;
+use crate::{Host, Interpreter};
+
+/// Outcome of executing a single instruction (or of the frame as a whole, once terminal).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionResult {
+    /// Frame should keep stepping.
+    Continue,
+    /// Stopped via `STOP`.
+    Stop,
+    /// Finished via `RETURN`.
+    Return,
+    /// Finished via `REVERT`.
+    Revert,
+    /// Popped more values than were on the stack.
+    StackUnderflow,
+    /// Pushed past [`crate::STACK_LIMIT`].
+    StackOverflow,
+    /// Ran out of gas.
+    OutOfGas,
+    /// `JUMP`/`JUMPI` targeted a byte that isn't a valid `JUMPDEST`.
+    InvalidJump,
+    /// Program counter landed on a byte that isn't a known opcode.
+    OpcodeNotFound,
+    /// Attempted a value-transferring `CALL` while `is_static` was set.
+    CallNotAllowedInsideStatic,
+    /// Attempted a storage write (or similar state change) while `is_static` was set.
+    StateChangeDuringStaticCall,
+    /// `CREATE`/`CREATE2` output exceeded [`crate::MAX_CODE_SIZE`].
+    CreateContractSizeLimit,
+    /// `CREATE`/`CREATE2` initcode exceeded [`crate::MAX_INITCODE_SIZE`].
+    CreateInitcodeSizeLimit,
+}
+
+impl InstructionResult {
+    /// Whether this is a "successful" terminal result (`STOP`/`RETURN`), as opposed to a revert
+    /// or an error.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Stop | Self::Return)
+    }
+}
+
+/// Dispatch `opcode` to its handler.
+///
+/// `H`/`SPEC` are threaded through for the rest of the instruction table (arithmetic, memory,
+/// storage, ...), which this excerpt doesn't otherwise touch; the CALL/CREATE/RETURN family
+/// below needs neither: CALL/CREATE hand the host interaction back to the caller via
+/// `next_action` instead of reaching into `host` themselves, and RETURN/REVERT only record the
+/// output range.
+pub fn eval<H: Host, SPEC: Spec>(opcode: u8, interp: &mut Interpreter, _host: &mut H) {
+    match opcode {
+        opcode::STOP => control::stop(interp),
+        opcode::JUMP => control::jump(interp),
+        opcode::JUMPI => control::jumpi(interp),
+        opcode::RETURN => control::return_(interp, InstructionResult::Return),
+        opcode::REVERT => control::return_(interp, InstructionResult::Revert),
+        opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+            control::call(opcode, interp)
+        }
+        opcode::CREATE | opcode::CREATE2 => control::create(opcode, interp),
+        _ => reject_invalid_opcode(interp, opcode),
+    }
+}
+
+/// Reject `opcode` as unrecognized, recording its `(pc, opcode)` before failing.
+///
+/// `step` has already advanced `instruction_pointer` past `opcode` by the time `eval` dispatches
+/// here, so `program_counter()` no longer points at the offending byte - this is the only place
+/// left to capture it accurately, the same way `instructions::control::jump_to` does for invalid
+/// jump targets.
+pub(crate) fn reject_invalid_opcode(interp: &mut Interpreter, opcode: u8) {
+    interp.last_invalid_opcode = Some((interp.program_counter().saturating_sub(1), opcode));
+    interp.instruction_result = InstructionResult::OpcodeNotFound;
+}
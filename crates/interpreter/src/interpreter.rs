@@ -1,25 +1,67 @@
 pub mod analysis;
 mod contract;
+pub mod meter;
 pub(crate) mod shared_memory;
 mod stack;
+mod status;
+pub mod tracing;
 
-pub use analysis::BytecodeLocked;
+pub use analysis::{BytecodeLocked, SharedCache};
 pub use contract::Contract;
+pub use meter::ComputeMeter;
 pub use shared_memory::SharedMemory;
 pub use stack::{Stack, STACK_LIMIT};
+pub use status::InterpreterStatus;
+pub use tracing::Eip3155Tracer;
 
-use crate::primitives::{Bytes, Spec};
+use crate::primitives::{Bytes, Spec, B256};
 use crate::{
     alloc::boxed::Box,
     instructions::{eval, InstructionResult},
-    Gas, Host,
+    CallInputs, CreateInputs, Gas, Host,
 };
 use alloc::rc::Rc;
-use core::cell::RefCell;
+use core::cell::{Ref, RefCell};
 use core::ops::Range;
 
 pub const CALL_STACK_LIMIT: u64 = 1024;
 
+/// The result of a single call to [`Interpreter::run`].
+///
+/// Instead of recursing back into the [`Host`] to perform calls and creates, `run` drives a
+/// single frame until it either finishes or needs the outer executor to do something on its
+/// behalf. The executor is expected to handle the action and feed the result back in via
+/// [`Interpreter::resume_with`].
+#[derive(Debug, Clone)]
+pub enum InterpreterAction {
+    /// A `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL` needs to be executed by the host.
+    Call { inputs: Box<CallInputs> },
+    /// A `CREATE`/`CREATE2` needs to be executed by the host.
+    Create { inputs: Box<CreateInputs> },
+    /// The frame finished executing and produced a final result.
+    Return {
+        result: InstructionResult,
+        /// Structured classification of `result`, with whatever context was still available
+        /// (offending program counter, oversized code length, ...) at the point execution
+        /// stopped.
+        status: InterpreterStatus,
+        output: Bytes,
+        /// Shared handle to the [`ComputeMeter`] this frame was metered with. It's a handle
+        /// rather than a snapshot so this stays agnostic to the concrete cost model; the
+        /// executor reads whatever `remaining`/`refunded` it needs straight off it.
+        gas: Rc<RefCell<dyn ComputeMeter>>,
+    },
+    /// No action, frame is still running. This variant should never be observed outside of the
+    /// interpreter itself.
+    None,
+}
+
+impl Default for InterpreterAction {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
 /// EIP-170: Contract code size limit
 ///
 /// By default this limit is 0x6000 (~25kb)
@@ -33,8 +75,12 @@ pub struct Interpreter {
     pub instruction_pointer: *const u8,
     /// Return is main control flag, it tell us if we should continue interpreter or break from it
     pub instruction_result: InstructionResult,
-    /// left gas. Memory gas can be found in Memory field.
-    pub gas: Gas,
+    /// Compute accounting for this frame, behind a [`ComputeMeter`] so a caller can plug in an
+    /// alternative cost model instead of being stuck with [`Gas`] - see [`Self::new`]. The
+    /// `Rc<RefCell<_>>` mirrors `shared_memory` below: [`InterpreterAction::Return`] hands out a
+    /// clone of the same handle rather than a by-value snapshot, which is what lets this stay
+    /// agnostic to the concrete meter.
+    pub gas: Rc<RefCell<dyn ComputeMeter>>,
     /// Shared memory.
     pub shared_memory: Rc<RefCell<SharedMemory>>,
     /// Stack.
@@ -47,6 +93,33 @@ pub struct Interpreter {
     pub is_static: bool,
     /// Contract information and invoking data
     pub contract: Box<Contract>,
+    /// Pending action produced by the last `step`, consumed and cleared by `run`.
+    ///
+    /// Set from inside `eval` by the CALL/CREATE family of opcodes instead of the
+    /// instruction reaching back into the `Host` directly; this is what lets `run` suspend a
+    /// frame and hand control back to the caller instead of recursing.
+    pub next_action: InterpreterAction,
+    /// `(pc, target)` of the `JUMP`/`JUMPI` that rejected `target`, captured at the point of
+    /// rejection since by the time the frame stops, `instruction_pointer` has already moved past
+    /// the offending instruction and the destination is long off the stack.
+    pub(crate) last_invalid_jump: Option<(usize, usize)>,
+    /// `(pc, opcode)` of the unrecognized opcode `eval` fell through on, captured at dispatch
+    /// time for the same reason as `last_invalid_jump`: by the time the frame stops,
+    /// `instruction_pointer` points one byte *past* the offending opcode.
+    pub(crate) last_invalid_opcode: Option<(usize, u8)>,
+    /// Program counter of the `CALL`/`CREATE` that was rejected for attempting a state change
+    /// while `is_static` was set, captured at the point of rejection for the same reason as
+    /// `last_invalid_jump`.
+    pub(crate) last_static_violation_pc: Option<usize>,
+    /// Initcode length that `CREATE`/`CREATE2` rejected against [`MAX_INITCODE_SIZE`], captured
+    /// in the same frame that read it off memory, before a child interpreter would have been
+    /// spawned for it.
+    pub(crate) last_initcode_len: Option<usize>,
+    /// `(ret_offset, ret_len)` of the `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL` currently
+    /// suspended on `next_action`, so `resume_with` knows where in `shared_memory` to copy the
+    /// callee's output once the host feeds it back in. Left `None` while a `CREATE`/`CREATE2` is
+    /// suspended instead, since those don't write return data into memory.
+    pub(crate) pending_call_return: Option<(usize, usize)>,
 }
 
 impl Interpreter {
@@ -55,15 +128,15 @@ impl Interpreter {
         unsafe { *self.instruction_pointer }
     }
 
-    /// Create new interpreter
+    /// Create a new interpreter, metered by `gas`.
     pub fn new(
         contract: Box<Contract>,
-        gas_limit: u64,
+        gas: Rc<RefCell<dyn ComputeMeter>>,
         is_static: bool,
         shared_memory: &Rc<RefCell<SharedMemory>>,
     ) -> Self {
         Self {
-            instruction_pointer: contract.bytecode.as_ptr(),
+            instruction_pointer: contract.bytecode.bytecode().as_ptr(),
             return_range: Range::default(),
             stack: Stack::new(),
             shared_memory: Rc::clone(shared_memory),
@@ -71,16 +144,46 @@ impl Interpreter {
             contract,
             instruction_result: InstructionResult::Continue,
             is_static,
-            gas: Gas::new(gas_limit),
+            gas,
+            next_action: InterpreterAction::None,
+            last_invalid_jump: None,
+            last_invalid_opcode: None,
+            last_static_violation_pc: None,
+            last_initcode_len: None,
+            pending_call_return: None,
         }
     }
 
+    /// Same as [`Self::new`], but metered by a plain gas limit using the default [`Gas`] model -
+    /// the common case when no alternative [`ComputeMeter`] is needed.
+    pub fn new_with_gas_limit(
+        contract: Box<Contract>,
+        gas_limit: u64,
+        is_static: bool,
+        shared_memory: &Rc<RefCell<SharedMemory>>,
+    ) -> Self {
+        Self::new(
+            contract,
+            Rc::new(RefCell::new(Gas::new(gas_limit))),
+            is_static,
+            shared_memory,
+        )
+    }
+
     pub fn contract(&self) -> &Contract {
         &self.contract
     }
 
-    pub fn gas(&self) -> &Gas {
-        &self.gas
+    pub fn gas(&self) -> Ref<'_, dyn ComputeMeter> {
+        self.gas.borrow()
+    }
+
+    /// Charge `amount` against the interpreter's metering. This is the one place the instruction
+    /// table should go through to charge for an operation; it's a thin wrapper over
+    /// [`ComputeMeter::consume`] so a host that plugs in an alternative metering model still
+    /// gets charged consistently.
+    pub fn consume_gas(&mut self, amount: u64) -> Result<(), InstructionResult> {
+        self.gas.borrow_mut().consume(amount)
     }
 
     /// Reference of interpreter stack.
@@ -93,7 +196,7 @@ impl Interpreter {
         // Safety: this is just subtraction of pointers, it is safe to do.
         unsafe {
             self.instruction_pointer
-                .offset_from(self.contract.bytecode.as_ptr()) as usize
+                .offset_from(self.contract.bytecode.bytecode().as_ptr()) as usize
         }
     }
 
@@ -109,31 +212,93 @@ impl Interpreter {
         eval::<H, SPEC>(opcode, self, host);
     }
 
-    /// loop steps until we are finished with execution
-    pub fn run<H: Host, SPEC: Spec>(&mut self, host: &mut H) -> InstructionResult {
+    /// Drive the interpreter one frame at a time, returning as soon as a CALL, CREATE or the
+    /// final RETURN needs to be handled by the caller.
+    ///
+    /// The instruction pointer is left wherever execution stopped, so calling `run` again after
+    /// the action has been serviced (via [`Self::resume_with`] for calls/creates) continues
+    /// exactly where it left off instead of restarting the frame.
+    pub fn run<H: Host, SPEC: Spec>(&mut self, host: &mut H) -> InterpreterAction {
         while self.instruction_result == InstructionResult::Continue {
-            self.step::<H, SPEC>(host)
+            self.step::<H, SPEC>(host);
+            if !matches!(self.next_action, InterpreterAction::None) {
+                return core::mem::take(&mut self.next_action);
+            }
+        }
+        InterpreterAction::Return {
+            result: self.instruction_result,
+            status: InterpreterStatus::from_result(self.instruction_result, self),
+            output: self.return_value(),
+            gas: Rc::clone(&self.gas),
         }
-        self.instruction_result
     }
 
-    /// loop steps until we are finished with execution
-    pub fn run_inspect<H: Host, SPEC: Spec>(&mut self, host: &mut H) -> InstructionResult {
+    /// Same as [`Self::run`] but calls `host.step`/`host.step_end` around every instruction.
+    pub fn run_inspect<H: Host, SPEC: Spec>(&mut self, host: &mut H) -> InterpreterAction {
         while self.instruction_result == InstructionResult::Continue {
             // step
             let ret = host.step(self);
             if ret != InstructionResult::Continue {
-                return ret;
+                self.instruction_result = ret;
+                break;
             }
             self.step::<H, SPEC>(host);
 
             // step ends
             let ret = host.step_end(self, self.instruction_result);
             if ret != InstructionResult::Continue {
-                return ret;
+                self.instruction_result = ret;
+                break;
+            }
+
+            if !matches!(self.next_action, InterpreterAction::None) {
+                return core::mem::take(&mut self.next_action);
+            }
+        }
+        InterpreterAction::Return {
+            result: self.instruction_result,
+            status: InterpreterStatus::from_result(self.instruction_result, self),
+            output: self.return_value(),
+            gas: Rc::clone(&self.gas),
+        }
+    }
+
+    /// Resume a frame that was suspended on a `Call`/`Create` action, feeding back the result of
+    /// that call/create.
+    ///
+    /// If the suspended action was a call, this copies `min(ret_len, return_data.len())` bytes of
+    /// `return_data` into `shared_memory` at the call's `ret_offset`, exactly as the CALL family
+    /// of opcodes specifies. It then repopulates `return_data_buffer`, merges in the callee's
+    /// refund, and pushes the success/address word expected by the CALL/CREATE opcode onto the
+    /// stack.
+    ///
+    /// Note what this deliberately does *not* do: neither `call`/`create` nor this method touch
+    /// `self.gas` for the gas forwarded to the callee (`CallInputs::gas_limit`/
+    /// `CreateInputs::gas_limit` are requests, not a deduction taken from this frame's meter).
+    /// Crediting back `gas.remaining()` here without ever having deducted the stipend up front
+    /// would only inflate this frame's gas out of thin air - accounting for the gas a call/create
+    /// actually spends is the executor's responsibility, alongside the rest of the call stack it
+    /// already owns.
+    ///
+    /// This does *not* free the `shared_memory` context that was pushed when the action was
+    /// issued - the callee already did that itself, via `return_value`, on its way out. Popping
+    /// it again here would desync the context stack against every other in-flight frame.
+    pub fn resume_with(&mut self, return_data: Bytes, gas: Gas, result_word: B256) {
+        if let Some((ret_offset, ret_len)) = self.pending_call_return.take() {
+            let copy_len = ret_len.min(return_data.len());
+            if copy_len > 0 {
+                // `ret_offset` was already validated against `usize::try_from` when `call` popped
+                // it; `copy_len` is bounded by `return_data.len()`, so this can't overflow.
+                let _ = self
+                    .shared_memory
+                    .borrow_mut()
+                    .set(ret_offset, &return_data[..copy_len]);
             }
         }
-        self.instruction_result
+        self.return_data_buffer = return_data;
+        self.gas.borrow_mut().refund(gas.refunded());
+        let _ = self.stack.push(result_word.into());
+        self.instruction_result = InstructionResult::Continue;
     }
 
     /// Copy and get the return value of the interpreter, if any.
@@ -142,12 +307,86 @@ impl Interpreter {
         let bytes = if self.return_range.start == usize::MAX {
             Bytes::new()
         } else {
-            Bytes::copy_from_slice(self.shared_memory.borrow().get_slice(
+            // `return_` already guarded `start + len` against overflow before ever setting
+            // `return_range`, so this can't come back `None`.
+            let slice = self.shared_memory.borrow_mut().get_slice(
                 self.return_range.start,
                 self.return_range.end - self.return_range.start,
-            ))
+            );
+            Bytes::copy_from_slice(slice.unwrap_or_default())
         };
         self.shared_memory.borrow_mut().free_context_memory();
         bytes
     }
 }
+
+/// Build a bare interpreter over `code`, metered with `u64::MAX` gas, for use by the test modules
+/// in this crate - shared so `instructions::control` and `interpreter::status` don't each keep
+/// their own copy of the same fixture.
+#[cfg(test)]
+pub(crate) fn interp_with_code(code: &[u8]) -> Interpreter {
+    use crate::primitives::{Address, Bytecode, U256};
+
+    let contract = Contract::new(
+        Bytes::new(),
+        Bytecode::new_raw(Bytes::copy_from_slice(code)),
+        B256::ZERO,
+        Address::ZERO,
+        Address::ZERO,
+        U256::ZERO,
+    );
+    let shared_memory = Rc::new(RefCell::new(SharedMemory::new()));
+    Interpreter::new_with_gas_limit(Box::new(contract), u64::MAX, false, &shared_memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::control;
+    use crate::opcode;
+    use crate::primitives::U256;
+
+    #[test]
+    fn call_resume_with_writes_output_into_caller_memory_without_double_freeing_context() {
+        let mut interp = interp_with_code(&[opcode::CALL]);
+        // CALL stack order is gas, to, value, argsOffset, argsLength, retOffset, retLength, with
+        // `gas` as the topmost (first popped); push bottom-to-top so the last value pushed ends
+        // up on top.
+        for v in [4u64, 9, 0, 0, 0, 0, 0] {
+            interp.stack.push(U256::from(v)).unwrap();
+        }
+        control::call(opcode::CALL, &mut interp);
+        assert!(
+            matches!(interp.next_action, InterpreterAction::Call { .. }),
+            "CALL must suspend via next_action"
+        );
+        assert_eq!(
+            interp.shared_memory.borrow().context_depth(),
+            2,
+            "call pushes a context for the callee"
+        );
+
+        // The callee would run in that context and free it itself, via `return_value`, on its
+        // way out. Driving an actual callee `Interpreter::run` here would need a `Host`/`Spec`
+        // pair, which live outside this crate slice, so simulate just that part directly.
+        interp.shared_memory.borrow_mut().free_context_memory();
+
+        interp.resume_with(
+            Bytes::copy_from_slice(&[0xaa, 0xbb, 0xcc, 0xdd]),
+            Gas::new(0),
+            B256::with_last_byte(1),
+        );
+
+        assert_eq!(
+            interp.shared_memory.borrow().context_depth(),
+            1,
+            "resume_with must not pop a second context on top of the callee's own free"
+        );
+        assert_eq!(
+            interp.shared_memory.borrow_mut().get_slice(9, 4),
+            Some(&[0xaa, 0xbb, 0xcc, 0xdd][..]),
+            "callee output must land at retOffset"
+        );
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+    }
+}
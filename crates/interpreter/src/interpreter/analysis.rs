@@ -0,0 +1,243 @@
+use crate::opcode;
+use crate::primitives::{Bytecode, Bytes, B256};
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+/// Number of bytes we pad the bytecode with so that reading past the end of the code during the
+/// last instruction never falls off the slice; every padding byte is `STOP`.
+const PADDING: usize = 33;
+
+/// Bytecode with a precomputed, shared jump-destination bitmap.
+///
+/// Producing the bitmap requires a full scan of the bytecode to account for multi-byte `PUSH`
+/// arguments (a `PUSH` immediate that happens to contain the `JUMPDEST` opcode must not be
+/// treated as a valid jump target). [`BytecodeLocked::new`] does that scan once; after that the
+/// bitmap is shared (`Rc`) across every [`Interpreter`](super::Interpreter) entering this
+/// contract.
+#[derive(Debug, Clone)]
+pub struct BytecodeLocked {
+    bytecode: Bytes,
+    len: usize,
+    jumpdest: Rc<JumpDestBitSet>,
+}
+
+impl BytecodeLocked {
+    /// Pad `bytecode` with trailing `STOP`s and scan it for valid `JUMPDEST`s.
+    pub fn new(bytecode: Bytecode) -> Self {
+        let len = bytecode.len();
+        let mut padded = Vec::with_capacity(len + PADDING);
+        padded.extend_from_slice(&bytecode.original_bytes());
+        padded.resize(len + PADDING, opcode::STOP);
+
+        Self {
+            jumpdest: Rc::new(JumpDestBitSet::analyze(&padded[..len])),
+            bytecode: Bytes::from(padded),
+            len,
+        }
+    }
+
+    /// Padded bytecode, safe to read `PADDING` bytes past `len()`.
+    pub fn bytecode(&self) -> &Bytes {
+        &self.bytecode
+    }
+
+    /// Length of the bytecode, excluding the trailing `STOP` padding.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if `pc` points at a valid `JUMPDEST`.
+    pub fn is_valid_jump(&self, pc: usize) -> bool {
+        self.jumpdest.is_valid(pc)
+    }
+}
+
+/// Packed bitmap of valid `JUMPDEST` positions, one bit per bytecode byte (64 per `u64` word),
+/// instead of a byte per position.
+#[derive(Debug)]
+struct JumpDestBitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl JumpDestBitSet {
+    fn analyze(code: &[u8]) -> Self {
+        let mut set = Self {
+            words: alloc::vec![0u64; code.len().div_ceil(64)],
+            len: code.len(),
+        };
+        let mut i = 0;
+        while i < code.len() {
+            let op = code[i];
+            if op == opcode::JUMPDEST {
+                set.set(i);
+                i += 1;
+            } else if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                i += (op - opcode::PUSH1) as usize + 2;
+            } else {
+                i += 1;
+            }
+        }
+        set
+    }
+
+    fn set(&mut self, pc: usize) {
+        self.words[pc / 64] |= 1u64 << (pc % 64);
+    }
+
+    fn is_valid(&self, pc: usize) -> bool {
+        pc < self.len && (self.words[pc / 64] & (1u64 << (pc % 64))) != 0
+    }
+}
+
+/// An LRU cache of [`BytecodeLocked`] analyses keyed by code hash, bounded by total bytecode
+/// bytes rather than entry count.
+///
+/// Re-running [`BytecodeLocked::new`] for every call into a hot contract is wasted work within a
+/// block; a `SharedCache` lets [`Contract::new_with_cache`](super::Contract::new_with_cache)
+/// look up an already-analyzed contract instead. One cache can be shared (cloning it is cheap and
+/// every clone refers to the same entries) across every frame in an executor.
+#[derive(Debug, Clone)]
+pub struct SharedCache {
+    inner: Rc<core::cell::RefCell<CacheInner>>,
+}
+
+#[derive(Debug)]
+struct CacheInner {
+    /// Code hash -> analyzed bytecode. `order` tracks recency of use (front = least recently
+    /// used): `insert`/`touch` push to the back, and eviction removes from the front.
+    entries: BTreeMap<B256, BytecodeLocked>,
+    order: Vec<B256>,
+    size_bytes: usize,
+    max_size_bytes: usize,
+}
+
+impl SharedCache {
+    /// Create a cache that evicts least-recently-used entries once the analyzed bytecode stored
+    /// in it exceeds `max_size_bytes`.
+    pub fn new(max_size_bytes: usize) -> Self {
+        Self {
+            inner: Rc::new(core::cell::RefCell::new(CacheInner {
+                entries: BTreeMap::new(),
+                order: Vec::new(),
+                size_bytes: 0,
+                max_size_bytes,
+            })),
+        }
+    }
+
+    /// Fetch the analyzed bytecode for `code_hash`, running `analyze` and inserting the result
+    /// on a miss.
+    pub fn get_or_analyze(
+        &self,
+        code_hash: B256,
+        analyze: impl FnOnce() -> BytecodeLocked,
+    ) -> BytecodeLocked {
+        let mut inner = self.inner.borrow_mut();
+        if let Some(hit) = inner.entries.get(&code_hash).cloned() {
+            inner.touch(code_hash);
+            return hit;
+        }
+
+        let analyzed = analyze();
+        inner.insert(code_hash, analyzed.clone());
+        analyzed
+    }
+}
+
+impl CacheInner {
+    fn touch(&mut self, code_hash: B256) {
+        if let Some(pos) = self.order.iter().position(|h| *h == code_hash) {
+            let hash = self.order.remove(pos);
+            self.order.push(hash);
+        }
+    }
+
+    fn insert(&mut self, code_hash: B256, bytecode: BytecodeLocked) {
+        self.size_bytes += bytecode.len();
+        self.entries.insert(code_hash, bytecode);
+        self.order.push(code_hash);
+
+        while self.size_bytes > self.max_size_bytes && !self.order.is_empty() {
+            let oldest = self.order.remove(0);
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.size_bytes = self.size_bytes.saturating_sub(evicted.len());
+            }
+        }
+    }
+}
+
+impl Default for SharedCache {
+    /// Defaults to a 16 MiB budget, generous enough for the hottest contracts in a block while
+    /// staying well under typical executor memory limits.
+    fn default() -> Self {
+        Self::new(16 * 1024 * 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(bytes: &[u8]) -> BytecodeLocked {
+        BytecodeLocked::new(Bytecode::new_raw(Bytes::copy_from_slice(bytes)))
+    }
+
+    #[test]
+    fn jumpdest_bitset_skips_push_immediates() {
+        // PUSH1 0x5b (JUMPDEST as immediate data, not an opcode), then a real JUMPDEST.
+        let bytecode = locked(&[opcode::PUSH1, opcode::JUMPDEST, opcode::JUMPDEST]);
+        assert!(!bytecode.is_valid_jump(1), "PUSH1 immediate must not count as JUMPDEST");
+        assert!(bytecode.is_valid_jump(2));
+        assert!(!bytecode.is_valid_jump(3), "out of bounds");
+    }
+
+    #[test]
+    fn shared_cache_hits_without_reanalyzing() {
+        let cache = SharedCache::new(1024);
+        let hash = B256::with_last_byte(1);
+        let mut analyze_calls = 0;
+        cache.get_or_analyze(hash, || {
+            analyze_calls += 1;
+            locked(&[opcode::STOP])
+        });
+        cache.get_or_analyze(hash, || {
+            analyze_calls += 1;
+            locked(&[opcode::STOP])
+        });
+        assert_eq!(analyze_calls, 1);
+    }
+
+    #[test]
+    fn shared_cache_evicts_least_recently_used_once_over_budget() {
+        // Budget fits exactly one 2-byte entry at a time.
+        let cache = SharedCache::new(2);
+        let a = B256::with_last_byte(1);
+        let b = B256::with_last_byte(2);
+
+        cache.get_or_analyze(a, || locked(&[opcode::STOP, opcode::STOP]));
+        cache.get_or_analyze(b, || locked(&[opcode::STOP, opcode::STOP]));
+
+        // `a` should have been evicted to make room for `b`, so fetching it re-analyzes.
+        let mut reanalyzed = false;
+        cache.get_or_analyze(a, || {
+            reanalyzed = true;
+            locked(&[opcode::STOP, opcode::STOP])
+        });
+        assert!(reanalyzed, "oldest entry should have been evicted");
+
+        // Re-inserting `a` pushed the cache over budget again, evicting `b` in turn, so
+        // fetching `b` re-analyzes too.
+        let mut b_reanalyzed = false;
+        cache.get_or_analyze(b, || {
+            b_reanalyzed = true;
+            locked(&[opcode::STOP, opcode::STOP])
+        });
+        assert!(b_reanalyzed, "budget of 2 can only ever hold one 2-byte entry");
+    }
+}
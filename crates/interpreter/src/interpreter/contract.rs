@@ -0,0 +1,55 @@
+use super::analysis::{BytecodeLocked, SharedCache};
+use crate::primitives::{Address, Bytecode, Bytes, B256, U256};
+
+/// Contract code and the data the currently executing call/create was invoked with.
+#[derive(Debug, Clone)]
+pub struct Contract {
+    /// Bytecode of the contract, already analyzed and padded.
+    pub bytecode: BytecodeLocked,
+    /// Hash of the unpadded contract bytecode.
+    pub hash: B256,
+    /// Calldata/constructor arguments.
+    pub input: Bytes,
+    /// Address of the contract that is being executed.
+    pub address: Address,
+    /// Caller of the contract.
+    pub caller: Address,
+    /// Value sent with the call/create.
+    pub value: U256,
+}
+
+impl Contract {
+    /// Analyze `bytecode` fresh every time, with no sharing across calls.
+    pub fn new(input: Bytes, bytecode: Bytecode, hash: B256, address: Address, caller: Address, value: U256) -> Self {
+        Self {
+            bytecode: BytecodeLocked::new(bytecode),
+            hash,
+            input,
+            address,
+            caller,
+            value,
+        }
+    }
+
+    /// Same as [`Contract::new`], but looks the analyzed bytecode up in `cache` first, keyed by
+    /// `hash`, and only re-analyzes on a miss.
+    pub fn new_with_cache(
+        input: Bytes,
+        bytecode: Bytecode,
+        hash: B256,
+        address: Address,
+        caller: Address,
+        value: U256,
+        cache: &SharedCache,
+    ) -> Self {
+        let bytecode = cache.get_or_analyze(hash, || BytecodeLocked::new(bytecode));
+        Self {
+            bytecode,
+            hash,
+            input,
+            address,
+            caller,
+            value,
+        }
+    }
+}
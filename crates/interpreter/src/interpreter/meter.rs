@@ -0,0 +1,60 @@
+use crate::instructions::InstructionResult;
+use crate::Gas;
+
+/// Abstracts gas/compute accounting behind a trait instead of hard-wiring the interpreter to a
+/// single cost model.
+///
+/// [`Interpreter::gas`](super::Interpreter::gas) holds an `Rc<RefCell<dyn ComputeMeter>>` rather
+/// than a concrete [`Gas`], so handing [`Interpreter::new`](super::Interpreter::new) a different
+/// implementation - e.g. a flat per-opcode compute-unit budget, or one that prices memory
+/// expansion differently - is enough to swap cost models, without forking any opcode
+/// implementations. [`Interpreter::new_with_gas_limit`](super::Interpreter::new_with_gas_limit)
+/// covers the common case of metering with [`Gas`] directly.
+pub trait ComputeMeter: core::fmt::Debug {
+    /// Charge `amount`, failing with [`InstructionResult::OutOfGas`] if it would exceed what's
+    /// left.
+    fn consume(&mut self, amount: u64) -> Result<(), InstructionResult>;
+
+    /// Units left before [`Self::consume`] starts failing.
+    fn remaining(&self) -> u64;
+
+    /// Add `amount` to the refund counter (e.g. `SSTORE` clearing a slot).
+    fn refund(&mut self, amount: i64);
+
+    /// Current refund counter.
+    fn refunded(&self) -> i64;
+
+    /// Credit back `amount` previously-charged units, undoing an earlier [`Self::consume`]. Not
+    /// currently called anywhere in this crate - gas forwarded to a `CALL`/`CREATE` is never
+    /// deducted from this meter in the first place (see
+    /// [`Interpreter::resume_with`](super::Interpreter::resume_with)), so there's nothing here to
+    /// credit back - but it's kept available for a host that does meter call/create gas through
+    /// this trait and needs the inverse of `consume`.
+    fn credit(&mut self, amount: u64);
+}
+
+impl ComputeMeter for Gas {
+    fn consume(&mut self, amount: u64) -> Result<(), InstructionResult> {
+        if self.record_cost(amount) {
+            Ok(())
+        } else {
+            Err(InstructionResult::OutOfGas)
+        }
+    }
+
+    fn remaining(&self) -> u64 {
+        Gas::remaining(self)
+    }
+
+    fn refund(&mut self, amount: i64) {
+        self.record_refund(amount);
+    }
+
+    fn refunded(&self) -> i64 {
+        Gas::refunded(self)
+    }
+
+    fn credit(&mut self, amount: u64) {
+        self.erase_cost(amount);
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+/// A memory buffer shared by every frame of a call stack.
+///
+/// Each nested call/create pushes a new "context" (offset into the shared buffer) so that frame
+/// only ever sees memory at or after its own offset, while still reusing the same backing
+/// allocation instead of each frame allocating its own `Vec`.
+#[derive(Debug, Default)]
+pub struct SharedMemory {
+    buffer: Vec<u8>,
+    /// Offsets into `buffer` at which each currently active context begins, outermost first.
+    checkpoints: Vec<usize>,
+    current_offset: usize,
+}
+
+impl SharedMemory {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            checkpoints: alloc::vec![0],
+            current_offset: 0,
+        }
+    }
+
+    /// Push a new context at the current end of the buffer; memory accesses are relative to it
+    /// until [`Self::free_context_memory`] pops it back off.
+    pub fn new_context_memory(&mut self) {
+        self.checkpoints.push(self.buffer.len());
+        self.current_offset = self.buffer.len();
+    }
+
+    /// Pop the most recently pushed context, discarding the memory it used.
+    pub fn free_context_memory(&mut self) {
+        if let Some(offset) = self.checkpoints.pop() {
+            self.buffer.truncate(offset);
+        }
+        self.current_offset = self.checkpoints.last().copied().unwrap_or(0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.current_offset
+    }
+
+    /// Number of contexts currently pushed, including the outermost one. Mainly useful for
+    /// tests asserting that `new_context_memory`/`free_context_memory` calls stay balanced.
+    pub fn context_depth(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Grow the buffer so it holds at least `new_size` bytes past `current_offset`. Returns
+    /// `None` (leaving the buffer untouched) instead of panicking if `current_offset + new_size`
+    /// would overflow `usize` - callers are expected to have already bounded `new_size` against
+    /// whatever offset/length it was derived from (see [`Self::get_slice`]/[`Self::set`]).
+    fn resize(&mut self, new_size: usize) -> Option<()> {
+        let needed = self.current_offset.checked_add(new_size)?;
+        if needed > self.buffer.len() {
+            self.buffer.resize(needed, 0);
+        }
+        Some(())
+    }
+
+    /// Get a slice of `len` bytes at `offset`, relative to the current context, resizing
+    /// (zero-filling) first if it reaches past what this context has touched so far - reading
+    /// untouched memory is valid EVM behavior and always reads as zero.
+    ///
+    /// Returns `None` instead of panicking if `offset + len` would overflow `usize` - a valid
+    /// (if absurd) stack operand for `CALL`/`CREATE`/`RETURN`/`REVERT` to pop.
+    pub fn get_slice(&mut self, offset: usize, len: usize) -> Option<&[u8]> {
+        let end = offset.checked_add(len)?;
+        self.resize(end)?;
+        let start = self.current_offset + offset;
+        Some(&self.buffer[start..start + len])
+    }
+
+    /// Set `data` at `offset`, relative to the current context, growing the buffer if needed.
+    ///
+    /// Returns `None` instead of panicking if `offset + data.len()` would overflow `usize`.
+    pub fn set(&mut self, offset: usize, data: &[u8]) -> Option<()> {
+        let end = offset.checked_add(data.len())?;
+        self.resize(end)?;
+        let start = self.current_offset + offset;
+        self.buffer[start..start + data.len()].copy_from_slice(data);
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_slice_zero_fills_memory_beyond_whats_been_touched() {
+        let mut mem = SharedMemory::new();
+        // Nothing has been written yet; reading past it is valid EVM behavior (reads as zero),
+        // not an out-of-bounds panic.
+        assert_eq!(mem.get_slice(9, 4), Some(&[0, 0, 0, 0][..]));
+    }
+
+    #[test]
+    fn get_slice_returns_none_instead_of_overflowing_on_a_huge_offset() {
+        let mut mem = SharedMemory::new();
+        assert_eq!(mem.get_slice(usize::MAX, 4), None);
+    }
+
+    #[test]
+    fn set_returns_none_instead_of_overflowing_on_a_huge_offset() {
+        let mut mem = SharedMemory::new();
+        assert_eq!(mem.set(usize::MAX, &[1, 2, 3, 4]), None);
+    }
+}
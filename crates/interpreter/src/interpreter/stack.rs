@@ -0,0 +1,152 @@
+use crate::instructions::InstructionResult;
+use crate::primitives::U256;
+use alloc::vec::Vec;
+
+/// Yellow Paper: the EVM operand stack never holds more than 1024 words.
+pub const STACK_LIMIT: usize = 1024;
+
+/// The EVM operand stack.
+#[derive(Debug, Clone)]
+pub struct Stack {
+    data: Vec<U256>,
+}
+
+impl Stack {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::with_capacity(STACK_LIMIT),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn data(&self) -> &Vec<U256> {
+        &self.data
+    }
+
+    /// Push a new word, failing if the stack is already at [`STACK_LIMIT`].
+    pub fn push(&mut self, value: U256) -> Result<(), InstructionResult> {
+        if self.data.len() >= STACK_LIMIT {
+            return Err(InstructionResult::StackOverflow);
+        }
+        self.data.push(value);
+        Ok(())
+    }
+
+    /// Pop the top word off the stack.
+    pub fn pop(&mut self) -> Result<U256, InstructionResult> {
+        self.data.pop().ok_or(InstructionResult::StackUnderflow)
+    }
+
+    /// Returns `true` if the stack holds at least `n` elements, without removing anything.
+    ///
+    /// Lets an opcode do a single up-front depth check instead of having each individual `pop`
+    /// bounds-check on its own.
+    pub fn has(&self, n: usize) -> bool {
+        self.data.len() >= n
+    }
+
+    /// Peek at the element `n` positions from the top (`n = 0` is the top element) without
+    /// removing it.
+    pub fn peek(&self, n: usize) -> Result<&U256, InstructionResult> {
+        if !self.has(n + 1) {
+            return Err(InstructionResult::StackUnderflow);
+        }
+        Ok(&self.data[self.data.len() - 1 - n])
+    }
+
+    /// Swap the top of the stack with the element `n` positions from the top.
+    pub fn swap_with_top(&mut self, n: usize) -> Result<(), InstructionResult> {
+        if !self.has(n + 1) {
+            return Err(InstructionResult::StackUnderflow);
+        }
+        let top = self.data.len() - 1;
+        self.data.swap(top, top - n);
+        Ok(())
+    }
+
+    /// Pop `N` elements at once, returned top-first (`result[0]` is what `pop()` would have
+    /// returned).
+    pub fn pop_n<const N: usize>(&mut self) -> Result<[U256; N], InstructionResult> {
+        if !self.has(N) {
+            return Err(InstructionResult::StackUnderflow);
+        }
+        let mut out = [U256::ZERO; N];
+        for slot in out.iter_mut() {
+            // Safety: `has(N)` was just checked, so this can't underflow.
+            *slot = self.data.pop().expect("stack underflow already checked");
+        }
+        Ok(out)
+    }
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack_of(values: &[u64]) -> Stack {
+        let mut stack = Stack::new();
+        for v in values {
+            stack.push(U256::from(*v)).unwrap();
+        }
+        stack
+    }
+
+    #[test]
+    fn has_reflects_current_depth() {
+        let stack = stack_of(&[1, 2, 3]);
+        assert!(stack.has(3));
+        assert!(!stack.has(4));
+    }
+
+    #[test]
+    fn peek_does_not_remove() {
+        let stack = stack_of(&[1, 2, 3]);
+        assert_eq!(*stack.peek(0).unwrap(), U256::from(3));
+        assert_eq!(*stack.peek(2).unwrap(), U256::from(1));
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.peek(3).unwrap_err(), InstructionResult::StackUnderflow);
+    }
+
+    #[test]
+    fn swap_with_top_swaps_in_place() {
+        let mut stack = stack_of(&[1, 2, 3]);
+        stack.swap_with_top(2).unwrap();
+        assert_eq!(stack.data(), &[U256::from(3), U256::from(2), U256::from(1)]);
+        assert_eq!(
+            stack.swap_with_top(3).unwrap_err(),
+            InstructionResult::StackUnderflow
+        );
+    }
+
+    #[test]
+    fn pop_n_returns_top_first_and_drains() {
+        let mut stack = stack_of(&[1, 2, 3]);
+        let [top, mid] = stack.pop_n::<2>().unwrap();
+        assert_eq!(top, U256::from(3));
+        assert_eq!(mid, U256::from(2));
+        assert_eq!(stack.len(), 1);
+    }
+
+    #[test]
+    fn pop_n_underflow_leaves_stack_untouched() {
+        let mut stack = stack_of(&[1]);
+        assert_eq!(
+            stack.pop_n::<2>().unwrap_err(),
+            InstructionResult::StackUnderflow
+        );
+        assert_eq!(stack.len(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use super::Interpreter;
+use crate::instructions::InstructionResult;
+
+/// A structured classification of why a frame stopped, carrying whatever context is available
+/// about the offending instruction instead of the single flat [`InstructionResult`] code.
+///
+/// This mirrors the trap/error taxonomies of runtimes like wasm's `UserTrap`: hosts that want to
+/// react programmatically (precise revert reasons, metrics, fuzzer minimization) match on this
+/// instead of re-deriving the category from the raw result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterStatus {
+    /// Frame is still running; should never be observed outside of the interpreter itself.
+    Continue,
+    /// Stopped normally via `STOP`.
+    Stopped,
+    /// Finished via `RETURN`.
+    Returned,
+    /// Finished via `REVERT`.
+    Reverted,
+    /// Popped more values than were on the stack.
+    StackUnderflow,
+    /// Pushed past [`super::STACK_LIMIT`].
+    StackOverflow,
+    /// Ran out of gas.
+    OutOfGas,
+    /// `JUMP`/`JUMPI` targeted a byte that isn't a valid `JUMPDEST`.
+    InvalidJump { pc: usize, target: usize },
+    /// Program counter landed on a byte that isn't a known opcode.
+    InvalidOpcode { pc: usize, opcode: u8 },
+    /// Attempted a value transfer or storage write while `is_static` was set.
+    StaticStateViolation { pc: usize },
+    /// `CREATE`/`CREATE2` output exceeded [`super::MAX_CODE_SIZE`].
+    ///
+    /// The offending length isn't carried here: it's the *deployed* code length, only known
+    /// once the init-code's child interpreter has returned and the host compares its output
+    /// against the limit, so it isn't available from this frame alone.
+    CreateCodeSizeLimit,
+    /// `CREATE`/`CREATE2` initcode exceeded [`super::MAX_INITCODE_SIZE`].
+    CreateInitcodeSizeLimit { len: usize },
+    /// Anything else; still carries the original flat result.
+    Other(InstructionResult),
+}
+
+impl InterpreterStatus {
+    /// Classify a terminal [`InstructionResult`], pulling whatever extra context `interp` still
+    /// has available (program counter, bytecode length, ...) at the point the frame stopped.
+    pub fn from_result(result: InstructionResult, interp: &Interpreter) -> Self {
+        match result {
+            InstructionResult::Continue => Self::Continue,
+            InstructionResult::Stop => Self::Stopped,
+            InstructionResult::Return => Self::Returned,
+            InstructionResult::Revert => Self::Reverted,
+            InstructionResult::StackUnderflow => Self::StackUnderflow,
+            InstructionResult::StackOverflow => Self::StackOverflow,
+            InstructionResult::OutOfGas => Self::OutOfGas,
+            InstructionResult::InvalidJump => {
+                // By the time a terminal result is classified, `step` has already advanced
+                // `instruction_pointer` past the rejecting `JUMP`/`JUMPI`, and the destination was
+                // popped off the stack before its validity was even known - so both `pc` and
+                // `target` were captured at the point of rejection instead, in
+                // `last_invalid_jump`.
+                let (pc, target) = interp.last_invalid_jump.unwrap_or((usize::MAX, usize::MAX));
+                Self::InvalidJump { pc, target }
+            }
+            InstructionResult::OpcodeNotFound => {
+                // Same reasoning as `InvalidJump`: `instruction_pointer` has moved past the
+                // unrecognized opcode by now, so both fields come from `last_invalid_opcode`,
+                // captured at dispatch time in `instructions::reject_invalid_opcode`.
+                let (pc, opcode) = interp.last_invalid_opcode.unwrap_or((usize::MAX, 0));
+                Self::InvalidOpcode { pc, opcode }
+            }
+            InstructionResult::CallNotAllowedInsideStatic
+            | InstructionResult::StateChangeDuringStaticCall => Self::StaticStateViolation {
+                pc: interp.last_static_violation_pc.unwrap_or(usize::MAX),
+            },
+            InstructionResult::CreateContractSizeLimit => Self::CreateCodeSizeLimit,
+            InstructionResult::CreateInitcodeSizeLimit => Self::CreateInitcodeSizeLimit {
+                // Recorded by `instructions::control::create` in the same frame that read the
+                // initcode length off memory, before it would have spawned a child interpreter.
+                len: interp.last_initcode_len.unwrap_or(usize::MAX),
+            },
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::interp_with_code;
+    use super::*;
+
+    #[test]
+    fn invalid_jump_carries_the_jumps_own_pc_not_the_instruction_after_it() {
+        // JUMP at pc 0, STOP padding after it; the instruction pointer would be at pc 1 by the
+        // time `step` dispatches into `jump`, so simulate that instead of setting the field by
+        // hand - that's exactly what the previous version of this test got wrong.
+        let mut interp = interp_with_code(&[crate::opcode::JUMP, crate::opcode::STOP]);
+        interp.instruction_pointer = unsafe { interp.instruction_pointer.add(1) };
+        crate::instructions::control::jump_to(&mut interp, crate::primitives::U256::from(7));
+
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::InvalidJump, &interp),
+            InterpreterStatus::InvalidJump { pc: 0, target: 7 }
+        );
+    }
+
+    #[test]
+    fn invalid_opcode_carries_the_unrecognized_bytes_own_pc_not_the_byte_after_it() {
+        // Unassigned opcode 0x0c at pc 0; simulate `step` having already moved the pointer to
+        // pc 1 before `eval` falls through to the catch-all arm.
+        let mut interp = interp_with_code(&[0x0c, crate::opcode::STOP]);
+        interp.instruction_pointer = unsafe { interp.instruction_pointer.add(1) };
+        crate::instructions::reject_invalid_opcode(&mut interp, 0x0c);
+
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::OpcodeNotFound, &interp),
+            InterpreterStatus::InvalidOpcode { pc: 0, opcode: 0x0c }
+        );
+    }
+
+    #[test]
+    fn create_initcode_size_limit_carries_the_rejected_length() {
+        let mut interp = interp_with_code(&[crate::opcode::STOP]);
+        interp.last_initcode_len = Some(123_456);
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::CreateInitcodeSizeLimit, &interp),
+            InterpreterStatus::CreateInitcodeSizeLimit { len: 123_456 }
+        );
+    }
+
+    #[test]
+    fn create_code_size_limit_carries_no_fabricated_length() {
+        let interp = interp_with_code(&[crate::opcode::STOP]);
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::CreateContractSizeLimit, &interp),
+            InterpreterStatus::CreateCodeSizeLimit
+        );
+    }
+
+    #[test]
+    fn passthrough_variants_map_one_to_one() {
+        let interp = interp_with_code(&[crate::opcode::STOP]);
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::Stop, &interp),
+            InterpreterStatus::Stopped
+        );
+        assert_eq!(
+            InterpreterStatus::from_result(InstructionResult::OutOfGas, &interp),
+            InterpreterStatus::OutOfGas
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! A built-in [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) step tracer, driven entirely
+//! through the `Host::step`/`Host::step_end` hook points that `Interpreter::run_inspect` already
+//! calls, so no change to the core interpreter loop is needed to use it.
+
+use crate::instructions::InstructionResult;
+use crate::{ComputeMeter, Interpreter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Records one JSON object per executed opcode in the standard `geth`-compatible EIP-3155
+/// format, plus a final summary line with the output, gas used and error (if any).
+///
+/// Wire it up from a `Host` impl's `step`/`step_end` by calling [`Self::on_step`] /
+/// [`Self::on_step_end`], and [`Self::on_finish`] once the outermost call returns.
+#[derive(Debug, Default)]
+pub struct Eip3155Tracer {
+    lines: Vec<String>,
+    depth: u64,
+    pending: Option<PendingStep>,
+}
+
+#[derive(Debug)]
+struct PendingStep {
+    pc: usize,
+    opcode: u8,
+    gas_before: u64,
+    stack: String,
+    memory_size: usize,
+}
+
+impl Eip3155Tracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One JSON line per opcode executed so far, plus the summary line once [`Self::on_finish`]
+    /// has been called.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Set the call depth reported on every subsequent step; callers track this themselves since
+    /// the `Interpreter` has no notion of the surrounding call stack.
+    pub fn set_depth(&mut self, depth: u64) {
+        self.depth = depth;
+    }
+
+    /// Call from `Host::step`, before the opcode at the current program counter executes.
+    pub fn on_step(&mut self, interp: &Interpreter) -> InstructionResult {
+        self.pending = Some(PendingStep {
+            pc: interp.program_counter(),
+            opcode: interp.current_opcode(),
+            gas_before: interp.gas.borrow().remaining(),
+            stack: stack_json(interp),
+            memory_size: interp.shared_memory.borrow().len(),
+        });
+        InstructionResult::Continue
+    }
+
+    /// Call from `Host::step_end`, right after the opcode executed.
+    pub fn on_step_end(
+        &mut self,
+        interp: &Interpreter,
+        result: InstructionResult,
+    ) -> InstructionResult {
+        if let Some(step) = self.pending.take() {
+            let gas_cost = step.gas_before.saturating_sub(interp.gas.borrow().remaining());
+            let mut line = String::new();
+            let _ = write!(
+                line,
+                "{{\"pc\":{},\"op\":{},\"gas\":\"0x{:x}\",\"gasCost\":\"0x{:x}\",\"memSize\":{},\"stack\":[{}],\"depth\":{},\"refund\":{}}}",
+                step.pc,
+                step.opcode,
+                step.gas_before,
+                gas_cost,
+                step.memory_size,
+                step.stack,
+                self.depth,
+                interp.gas.borrow().refunded(),
+            );
+            self.lines.push(line);
+        }
+        result
+    }
+
+    /// Append the final summary line once the outermost call has finished executing.
+    pub fn on_finish(&mut self, result: InstructionResult, output: &[u8], gas_used: u64) {
+        let error = if result == InstructionResult::Return || result == InstructionResult::Stop {
+            String::from("null")
+        } else {
+            format!("\"{:?}\"", result)
+        };
+        let mut line = String::new();
+        let _ = write!(
+            line,
+            "{{\"output\":\"0x{}\",\"gasUsed\":\"0x{:x}\",\"error\":{}}}",
+            hex(output),
+            gas_used,
+            error,
+        );
+        self.lines.push(line);
+    }
+}
+
+fn stack_json(interp: &Interpreter) -> String {
+    let mut out = String::new();
+    for (i, value) in interp.stack.data().iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "\"0x{:x}\"", value);
+    }
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out
+}
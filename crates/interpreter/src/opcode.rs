@@ -0,0 +1,19 @@
+//! Raw EVM opcode byte values used by bytecode analysis and instruction dispatch.
+
+pub const STOP: u8 = 0x00;
+
+pub const JUMP: u8 = 0x56;
+pub const JUMPI: u8 = 0x57;
+pub const JUMPDEST: u8 = 0x5b;
+
+pub const PUSH1: u8 = 0x60;
+pub const PUSH32: u8 = 0x7f;
+
+pub const CREATE: u8 = 0xf0;
+pub const CALL: u8 = 0xf1;
+pub const CALLCODE: u8 = 0xf2;
+pub const RETURN: u8 = 0xf3;
+pub const DELEGATECALL: u8 = 0xf4;
+pub const CREATE2: u8 = 0xf5;
+pub const STATICCALL: u8 = 0xfa;
+pub const REVERT: u8 = 0xfd;